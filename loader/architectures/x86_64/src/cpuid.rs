@@ -0,0 +1,382 @@
+//! Access to processor identification and feature information.
+
+use core::arch::asm;
+
+/// The first extended `CPUID` leaf.
+pub const EXTENDED_LEAF_BASE: u32 = 0x8000_0000;
+
+/// The registers returned by a `CPUID` invocation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CpuidResult {
+    /// The value of `eax`.
+    pub eax: u32,
+    /// The value of `ebx`.
+    pub ebx: u32,
+    /// The value of `ecx`.
+    pub ecx: u32,
+    /// The value of `edx`.
+    pub edx: u32,
+}
+
+/// Executes `CPUID` with the given `leaf` and `subleaf`.
+///
+/// Returns [`None`] if `leaf` is greater than the maximum supported leaf of its range. Only the
+/// standard range (below [`EXTENDED_LEAF_BASE`]) and the extended range (starting at
+/// [`EXTENDED_LEAF_BASE`]) are supported.
+pub fn cpuid(leaf: u32, subleaf: u32) -> Option<CpuidResult> {
+    let max_leaf = if leaf >= EXTENDED_LEAF_BASE {
+        max_extended_leaf()?
+    } else {
+        max_leaf()
+    };
+
+    if leaf > max_leaf {
+        return None;
+    }
+
+    Some(cpuid_unchecked(leaf, subleaf))
+}
+
+/// Returns the maximum supported standard `CPUID` leaf.
+pub fn max_leaf() -> u32 {
+    cpuid_unchecked(0, 0).eax
+}
+
+/// Returns the maximum supported extended `CPUID` leaf, or [`None`] if the extended range is not
+/// supported.
+pub fn max_extended_leaf() -> Option<u32> {
+    let max_extended_leaf = cpuid_unchecked(EXTENDED_LEAF_BASE, 0).eax;
+    if max_extended_leaf < EXTENDED_LEAF_BASE {
+        return None;
+    }
+
+    Some(max_extended_leaf)
+}
+
+/// Executes `CPUID` with the given `leaf` and `subleaf`.
+///
+/// This does not check that `leaf` is supported by the current processor. If it is not, the
+/// returned values are processor specific and must not be interpreted as the requested leaf. Use
+/// [`cpuid()`] unless support for `leaf` has already been checked.
+pub fn cpuid_unchecked(leaf: u32, subleaf: u32) -> CpuidResult {
+    let eax: u32;
+    let ebx: u32;
+    let ecx: u32;
+    let edx: u32;
+
+    // SAFETY:
+    // `CPUID` is supported on every `x86_64` processor and has no side effects. `rbx` is saved
+    // and restored since LLVM reserves it.
+    unsafe {
+        asm!(
+            "mov {ebx:r}, rbx",
+            "cpuid",
+            "xchg {ebx:r}, rbx",
+            ebx = out(reg) ebx,
+            inout("eax") leaf => eax,
+            inout("ecx") subleaf => ecx,
+            out("edx") edx,
+            options(nomem, nostack, preserves_flags)
+        )
+    }
+
+    CpuidResult { eax, ebx, ecx, edx }
+}
+
+/// The deterministic cache parameters leaf.
+const CACHE_PARAMETERS_LEAF: u32 = 0x4;
+/// The extended topology enumeration leaf.
+const TOPOLOGY_LEAF: u32 = 0xB;
+
+/// Returns an iterator over the caches described by the deterministic cache parameters leaf.
+///
+/// The iterator is empty if the current processor does not implement the leaf.
+pub fn cache_info() -> CacheInfoIter {
+    CacheInfoIter {
+        subleaf: 0,
+        done: max_leaf() < CACHE_PARAMETERS_LEAF,
+    }
+}
+
+/// An iterator over the [`CacheInfo`] reported by the current processor.
+#[derive(Clone, Debug)]
+pub struct CacheInfoIter {
+    /// The next subleaf to query.
+    subleaf: u32,
+    /// Whether the terminating subleaf has been reached.
+    done: bool,
+}
+
+impl Iterator for CacheInfoIter {
+    type Item = CacheInfo;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = cpuid_unchecked(CACHE_PARAMETERS_LEAF, self.subleaf);
+        let Some(cache) = CacheInfo::from_cpuid(result) else {
+            self.done = true;
+            return None;
+        };
+        self.subleaf += 1;
+
+        Some(cache)
+    }
+}
+
+/// The type of a cache.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CacheType {
+    /// A data cache.
+    Data,
+    /// An instruction cache.
+    Instruction,
+    /// A cache holding both instructions and data.
+    Unified,
+    /// A cache type reserved by the architecture.
+    Reserved(u8),
+}
+
+/// The parameters of a single cache.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CacheInfo {
+    /// The type of the cache.
+    pub cache_type: CacheType,
+    /// The level of the cache, starting at 1.
+    pub level: u8,
+    /// Whether the cache is fully associative.
+    pub fully_associative: bool,
+    /// The maximum number of logical processors sharing the cache.
+    pub max_sharing_logical_processors: u32,
+    /// The size, in bytes, of a cache line.
+    pub line_size: u32,
+    /// The number of physical line partitions.
+    pub partitions: u32,
+    /// The number of ways of associativity.
+    pub ways: u32,
+    /// The number of sets.
+    pub sets: u32,
+    /// Whether the cache is inclusive of lower cache levels.
+    pub inclusive: bool,
+}
+
+impl CacheInfo {
+    /// Decodes the result of a deterministic cache parameters leaf subleaf.
+    ///
+    /// Returns [`None`] if `result` is the terminating subleaf, which reports no cache.
+    pub const fn from_cpuid(result: CpuidResult) -> Option<Self> {
+        let cache_type = match result.eax & 0x1F {
+            0 => return None,
+            1 => CacheType::Data,
+            2 => CacheType::Instruction,
+            3 => CacheType::Unified,
+            value => CacheType::Reserved(value as u8),
+        };
+
+        Some(Self {
+            cache_type,
+            level: ((result.eax >> 5) & 0b111) as u8,
+            fully_associative: result.eax & (1 << 9) != 0,
+            max_sharing_logical_processors: ((result.eax >> 14) & 0xFFF) + 1,
+            line_size: (result.ebx & 0xFFF) + 1,
+            partitions: ((result.ebx >> 12) & 0x3FF) + 1,
+            ways: ((result.ebx >> 22) & 0x3FF) + 1,
+            sets: result.ecx + 1,
+            inclusive: result.edx & (1 << 1) != 0,
+        })
+    }
+
+    /// Returns the total size of the cache in bytes.
+    pub const fn size(&self) -> u64 {
+        self.ways as u64 * self.partitions as u64 * self.line_size as u64 * self.sets as u64
+    }
+}
+
+/// Returns an iterator over the topology levels described by the extended topology enumeration
+/// leaf.
+///
+/// The iterator is empty if the current processor does not implement the leaf.
+pub fn topology_levels() -> TopologyLevelIter {
+    TopologyLevelIter {
+        subleaf: 0,
+        done: max_leaf() < TOPOLOGY_LEAF,
+    }
+}
+
+/// An iterator over the [`TopologyLevel`]s reported by the current processor.
+#[derive(Clone, Debug)]
+pub struct TopologyLevelIter {
+    /// The next subleaf to query.
+    subleaf: u32,
+    /// Whether the terminating subleaf has been reached.
+    done: bool,
+}
+
+impl Iterator for TopologyLevelIter {
+    type Item = TopologyLevel;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = cpuid_unchecked(TOPOLOGY_LEAF, self.subleaf);
+        let Some(level) = TopologyLevel::from_cpuid(result) else {
+            self.done = true;
+            return None;
+        };
+        self.subleaf += 1;
+
+        Some(level)
+    }
+}
+
+/// The type of a processor topology level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TopologyLevelType {
+    /// The simultaneous multithreading level.
+    Smt,
+    /// The processor core level.
+    Core,
+    /// A topology level type reserved by the architecture.
+    Reserved(u8),
+}
+
+/// A single level of the processor topology.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TopologyLevel {
+    /// The type of this level.
+    pub level_type: TopologyLevelType,
+    /// The number of bits to shift the x2APIC ID right to obtain the ID of the next level.
+    pub x2apic_id_shift: u8,
+    /// The number of logical processors at this level.
+    pub logical_processors: u16,
+    /// The x2APIC ID of the current logical processor.
+    pub x2apic_id: u32,
+}
+
+impl TopologyLevel {
+    /// Decodes the result of an extended topology enumeration leaf subleaf.
+    ///
+    /// Returns [`None`] if `result` is the terminating subleaf, which reports an invalid level
+    /// type.
+    pub const fn from_cpuid(result: CpuidResult) -> Option<Self> {
+        let level_type = match (result.ecx >> 8) & 0xFF {
+            0 => return None,
+            1 => TopologyLevelType::Smt,
+            2 => TopologyLevelType::Core,
+            value => TopologyLevelType::Reserved(value as u8),
+        };
+
+        Some(Self {
+            level_type,
+            x2apic_id_shift: (result.eax & 0x1F) as u8,
+            logical_processors: (result.ebx & 0xFFFF) as u16,
+            x2apic_id: result.edx,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A level 2 unified cache with 64-byte lines, 1 partition, 16 ways, and 1024 sets, shared by
+    /// 2 logical processors.
+    const L2_CACHE: CpuidResult = CpuidResult {
+        eax: 3 | (2 << 5) | (1 << 14),
+        ebx: 63 | (15 << 22),
+        ecx: 1023,
+        edx: 1 << 1,
+    };
+
+    #[test]
+    fn cache_fields_are_offset_by_one() {
+        let cache = CacheInfo::from_cpuid(L2_CACHE).unwrap();
+
+        assert_eq!(cache.cache_type, CacheType::Unified);
+        assert_eq!(cache.level, 2);
+        assert!(!cache.fully_associative);
+        assert_eq!(cache.max_sharing_logical_processors, 2);
+        assert_eq!(cache.line_size, 64);
+        assert_eq!(cache.partitions, 1);
+        assert_eq!(cache.ways, 16);
+        assert_eq!(cache.sets, 1024);
+        assert!(cache.inclusive);
+        assert_eq!(cache.size(), 1024 * 1024);
+    }
+
+    #[test]
+    fn cache_types_are_decoded() {
+        let with_type = |cache_type| {
+            CacheInfo::from_cpuid(CpuidResult {
+                eax: cache_type,
+                ..L2_CACHE
+            })
+            .map(|cache| cache.cache_type)
+        };
+
+        assert_eq!(with_type(1), Some(CacheType::Data));
+        assert_eq!(with_type(2), Some(CacheType::Instruction));
+        assert_eq!(with_type(3), Some(CacheType::Unified));
+        assert_eq!(with_type(4), Some(CacheType::Reserved(4)));
+        assert_eq!(with_type(0x1F), Some(CacheType::Reserved(0x1F)));
+    }
+
+    #[test]
+    fn null_cache_type_terminates() {
+        let result = CpuidResult {
+            eax: L2_CACHE.eax & !0x1F,
+            ..L2_CACHE
+        };
+
+        assert_eq!(CacheInfo::from_cpuid(result), None);
+    }
+
+    /// The core level of a processor with 8 logical processors whose x2APIC IDs are shifted by 4
+    /// bits to reach the package level.
+    const CORE_LEVEL: CpuidResult = CpuidResult {
+        eax: 4,
+        ebx: 8,
+        ecx: 1 | (2 << 8),
+        edx: 0x13,
+    };
+
+    #[test]
+    fn topology_fields_are_decoded() {
+        let level = TopologyLevel::from_cpuid(CORE_LEVEL).unwrap();
+
+        assert_eq!(level.level_type, TopologyLevelType::Core);
+        assert_eq!(level.x2apic_id_shift, 4);
+        assert_eq!(level.logical_processors, 8);
+        assert_eq!(level.x2apic_id, 0x13);
+    }
+
+    #[test]
+    fn topology_types_are_decoded() {
+        let with_type = |level_type: u32| {
+            TopologyLevel::from_cpuid(CpuidResult {
+                ecx: level_type << 8,
+                ..CORE_LEVEL
+            })
+            .map(|level| level.level_type)
+        };
+
+        assert_eq!(with_type(1), Some(TopologyLevelType::Smt));
+        assert_eq!(with_type(2), Some(TopologyLevelType::Core));
+        assert_eq!(with_type(3), Some(TopologyLevelType::Reserved(3)));
+        assert_eq!(with_type(0xFF), Some(TopologyLevelType::Reserved(0xFF)));
+    }
+
+    #[test]
+    fn invalid_topology_type_terminates() {
+        let result = CpuidResult {
+            ecx: 2,
+            ..CORE_LEVEL
+        };
+
+        assert_eq!(TopologyLevel::from_cpuid(result), None);
+    }
+}
@@ -0,0 +1,32 @@
+//! Wrappers around individual `x86_64` instructions.
+
+use core::arch::asm;
+
+/// Returns the current value of the processor's timestamp counter.
+pub fn rdtsc() -> u64 {
+    let low: u32;
+    let high: u32;
+
+    // SAFETY:
+    // `rdtsc` only reads the timestamp counter.
+    unsafe {
+        asm!(
+            "rdtsc",
+            out("eax") low,
+            out("edx") high,
+            options(nomem, nostack, preserves_flags)
+        )
+    }
+
+    (u64::from(high) << 32) | u64::from(low)
+}
+
+/// Disables interrupts and halts the processor forever.
+pub fn halt_loop() -> ! {
+    loop {
+        // SAFETY:
+        // Disabling interrupts and halting cannot violate memory safety, and this function never
+        // returns to code that might expect interrupts to be enabled.
+        unsafe { asm!("cli", "hlt", options(nomem, nostack)) }
+    }
+}
@@ -0,0 +1,290 @@
+//! Access to the `x86_64` I/O port address space.
+
+use core::{arch::asm, marker::PhantomData, mem};
+
+/// Reads a byte from `port`.
+///
+/// # Safety
+///
+/// Reading from `port` must not violate any invariants the rest of the program relies upon.
+pub unsafe fn inb(port: u16) -> u8 {
+    let value: u8;
+
+    // SAFETY:
+    // The caller guarantees that reading from `port` is sound.
+    unsafe {
+        asm!("in al, dx", in("dx") port, out("al") value, options(nomem, nostack, preserves_flags))
+    }
+
+    value
+}
+
+/// Writes the byte `value` to `port`.
+///
+/// # Safety
+///
+/// Writing `value` to `port` must not violate any invariants the rest of the program relies upon.
+pub unsafe fn outb(port: u16, value: u8) {
+    // SAFETY:
+    // The caller guarantees that writing `value` to `port` is sound.
+    unsafe {
+        asm!("out dx, al", in("dx") port, in("al") value, options(nomem, nostack, preserves_flags))
+    }
+}
+
+/// Reads a word from `port`.
+///
+/// # Safety
+///
+/// Reading from `port` must not violate any invariants the rest of the program relies upon.
+pub unsafe fn inw(port: u16) -> u16 {
+    let value: u16;
+
+    // SAFETY:
+    // The caller guarantees that reading from `port` is sound.
+    unsafe {
+        asm!("in ax, dx", in("dx") port, out("ax") value, options(nomem, nostack, preserves_flags))
+    }
+
+    value
+}
+
+/// Writes the word `value` to `port`.
+///
+/// # Safety
+///
+/// Writing `value` to `port` must not violate any invariants the rest of the program relies upon.
+pub unsafe fn outw(port: u16, value: u16) {
+    // SAFETY:
+    // The caller guarantees that writing `value` to `port` is sound.
+    unsafe {
+        asm!("out dx, ax", in("dx") port, in("ax") value, options(nomem, nostack, preserves_flags))
+    }
+}
+
+/// Reads a doubleword from `port`.
+///
+/// # Safety
+///
+/// Reading from `port` must not violate any invariants the rest of the program relies upon.
+pub unsafe fn inl(port: u16) -> u32 {
+    let value: u32;
+
+    // SAFETY:
+    // The caller guarantees that reading from `port` is sound.
+    unsafe {
+        asm!("in eax, dx", in("dx") port, out("eax") value, options(nomem, nostack, preserves_flags))
+    }
+
+    value
+}
+
+/// Writes the doubleword `value` to `port`.
+///
+/// # Safety
+///
+/// Writing `value` to `port` must not violate any invariants the rest of the program relies upon.
+pub unsafe fn outl(port: u16, value: u32) {
+    // SAFETY:
+    // The caller guarantees that writing `value` to `port` is sound.
+    unsafe {
+        asm!("out dx, eax", in("dx") port, in("eax") value, options(nomem, nostack, preserves_flags))
+    }
+}
+
+/// A value that can be transferred through an I/O port.
+pub trait PortValue: Copy + sealed::Sealed {
+    /// Reads a value of this type from `port`.
+    ///
+    /// # Safety
+    ///
+    /// Reading from `port` must not violate any invariants the rest of the program relies upon.
+    unsafe fn read_from_port(port: u16) -> Self;
+
+    /// Writes `value` to `port`.
+    ///
+    /// # Safety
+    ///
+    /// Writing `value` to `port` must not violate any invariants the rest of the program relies
+    /// upon.
+    unsafe fn write_to_port(port: u16, value: Self);
+}
+
+impl PortValue for u8 {
+    unsafe fn read_from_port(port: u16) -> Self {
+        // SAFETY:
+        // The caller upholds the safety requirements of `inb`.
+        unsafe { inb(port) }
+    }
+
+    unsafe fn write_to_port(port: u16, value: Self) {
+        // SAFETY:
+        // The caller upholds the safety requirements of `outb`.
+        unsafe { outb(port, value) }
+    }
+}
+
+impl PortValue for u16 {
+    unsafe fn read_from_port(port: u16) -> Self {
+        // SAFETY:
+        // The caller upholds the safety requirements of `inw`.
+        unsafe { inw(port) }
+    }
+
+    unsafe fn write_to_port(port: u16, value: Self) {
+        // SAFETY:
+        // The caller upholds the safety requirements of `outw`.
+        unsafe { outw(port, value) }
+    }
+}
+
+impl PortValue for u32 {
+    unsafe fn read_from_port(port: u16) -> Self {
+        // SAFETY:
+        // The caller upholds the safety requirements of `inl`.
+        unsafe { inl(port) }
+    }
+
+    unsafe fn write_to_port(port: u16, value: Self) {
+        // SAFETY:
+        // The caller upholds the safety requirements of `outl`.
+        unsafe { outl(port, value) }
+    }
+}
+
+/// A single I/O port that transfers values of type `T`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Port<T: PortValue> {
+    /// The address of the port.
+    port: u16,
+    /// The type of value transferred through the port.
+    phantom: PhantomData<T>,
+}
+
+impl<T: PortValue> Port<T> {
+    /// Creates a new [`Port`] at `port`.
+    pub const fn new(port: u16) -> Self {
+        Self {
+            port,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Returns the address of this [`Port`].
+    pub const fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Reads a value from this [`Port`].
+    ///
+    /// # Safety
+    ///
+    /// Reading from this [`Port`] must not violate any invariants the rest of the program relies
+    /// upon.
+    pub unsafe fn read(&self) -> T {
+        // SAFETY:
+        // The caller guarantees that reading from this port is sound.
+        unsafe { T::read_from_port(self.port) }
+    }
+
+    /// Writes `value` to this [`Port`].
+    ///
+    /// # Safety
+    ///
+    /// Writing `value` to this [`Port`] must not violate any invariants the rest of the program
+    /// relies upon.
+    pub unsafe fn write(&self, value: T) {
+        // SAFETY:
+        // The caller guarantees that writing `value` to this port is sound.
+        unsafe { T::write_to_port(self.port, value) }
+    }
+}
+
+/// A contiguous range of I/O ports, such as the registers of a device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PortRange {
+    /// The address of the first port in the range.
+    base: u16,
+    /// The number of ports in the range.
+    length: u16,
+}
+
+impl PortRange {
+    /// Creates a new [`PortRange`] of `length` ports starting at `base`.
+    ///
+    /// Returns [`None`] if the range extends past the end of the I/O port address space.
+    pub const fn new(base: u16, length: u16) -> Option<Self> {
+        if (base as u32) + (length as u32) > u16::MAX as u32 + 1 {
+            return None;
+        }
+
+        Some(Self { base, length })
+    }
+
+    /// Returns the address of the first port in this [`PortRange`].
+    pub const fn base(&self) -> u16 {
+        self.base
+    }
+
+    /// Returns the number of ports in this [`PortRange`].
+    pub const fn length(&self) -> u16 {
+        self.length
+    }
+
+    /// Returns the [`Port`] located `offset` ports into this [`PortRange`].
+    ///
+    /// Returns [`None`] if a `T` transferred through the port would not lie entirely within this
+    /// [`PortRange`].
+    pub fn port<T: PortValue>(&self, offset: u16) -> Option<Port<T>> {
+        let end = u32::from(offset) + mem::size_of::<T>() as u32;
+        if end > u32::from(self.length) {
+            return None;
+        }
+
+        Some(Port::new(self.base + offset))
+    }
+}
+
+/// Prevents [`PortValue`] from being implemented outside of this module.
+mod sealed {
+    /// Supertrait of [`PortValue`][super::PortValue].
+    pub trait Sealed {}
+
+    impl Sealed for u8 {}
+    impl Sealed for u16 {}
+    impl Sealed for u32 {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range_may_end_at_last_port() {
+        let range = PortRange::new(0xFFF0, 0x10).unwrap();
+
+        assert_eq!(range.port::<u8>(0xF).map(|port| port.port()), Some(0xFFFF));
+        assert_eq!(range.port::<u16>(0xE).map(|port| port.port()), Some(0xFFFE));
+        assert_eq!(range.port::<u16>(0xF), None);
+    }
+
+    #[test]
+    fn range_past_address_space_is_rejected() {
+        assert_eq!(PortRange::new(0xFFF0, 0x11), None);
+        assert_eq!(PortRange::new(0xFFFF, 0xFFFF), None);
+    }
+
+    #[test]
+    fn u32_port_must_fit_in_range() {
+        let range = PortRange::new(0xCF8, 8).unwrap();
+
+        assert_eq!(range.port::<u32>(4).map(|port| port.port()), Some(0xCFC));
+        assert_eq!(range.port::<u32>(range.length() - 2), None);
+        assert_eq!(
+            range
+                .port::<u16>(range.length() - 2)
+                .map(|port| port.port()),
+            Some(0xCFE)
+        );
+    }
+}
@@ -1 +1,11 @@
 //! Support code for the `x86_64` architecture.
+
+#![no_std]
+
+pub mod cpuid;
+pub mod instructions;
+pub mod io_port;
+pub mod msr;
+pub mod paging;
+pub mod registers;
+pub mod serial;
@@ -0,0 +1,210 @@
+//! Access to model-specific registers.
+
+use core::arch::asm;
+
+/// Reads the model-specific register identified by `msr`.
+///
+/// # Safety
+///
+/// The processor must be executing at privilege level 0 and `msr` must be a model-specific
+/// register that the current processor implements.
+pub unsafe fn rdmsr(msr: u32) -> u64 {
+    let low: u32;
+    let high: u32;
+
+    // SAFETY:
+    // The caller guarantees that `rdmsr` is permitted and that `msr` is implemented.
+    unsafe {
+        asm!(
+            "rdmsr",
+            in("ecx") msr,
+            out("eax") low,
+            out("edx") high,
+            options(nomem, nostack, preserves_flags)
+        )
+    }
+
+    (u64::from(high) << 32) | u64::from(low)
+}
+
+/// Writes `value` to the model-specific register identified by `msr`.
+///
+/// # Safety
+///
+/// The processor must be executing at privilege level 0, `msr` must be a model-specific register
+/// that the current processor implements, and `value` must be valid for `msr`. Writing `value`
+/// must not violate any invariants the rest of the program relies upon.
+pub unsafe fn wrmsr(msr: u32, value: u64) {
+    let low = value as u32;
+    let high = (value >> 32) as u32;
+
+    // SAFETY:
+    // The caller guarantees that `wrmsr` is permitted and that `value` is valid for `msr`.
+    unsafe {
+        asm!(
+            "wrmsr",
+            in("ecx") msr,
+            in("eax") low,
+            in("edx") high,
+            options(nostack, preserves_flags)
+        )
+    }
+}
+
+/// The value of the `IA32_EFER` model-specific register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Efer(u64);
+
+impl Efer {
+    /// The index of the `IA32_EFER` model-specific register.
+    pub const MSR: u32 = 0xC000_0080;
+
+    /// System call extensions (`SYSCALL`/`SYSRET`) enable.
+    pub const SCE: u64 = 1 << 0;
+    /// Long mode enable.
+    pub const LME: u64 = 1 << 8;
+    /// Long mode active.
+    pub const LMA: u64 = 1 << 10;
+    /// No-execute enable.
+    pub const NXE: u64 = 1 << 11;
+
+    /// Reads the current value of `IA32_EFER`.
+    ///
+    /// # Safety
+    ///
+    /// The processor must be executing at privilege level 0 and must implement `IA32_EFER`.
+    pub unsafe fn read() -> Self {
+        // SAFETY:
+        // The caller guarantees that `IA32_EFER` can be read.
+        Self(unsafe { rdmsr(Self::MSR) })
+    }
+
+    /// Writes `self` to `IA32_EFER`.
+    ///
+    /// # Safety
+    ///
+    /// The processor must be executing at privilege level 0 and must implement `IA32_EFER` along
+    /// with every feature `self` enables. Changing [`Efer::LME`] or [`Efer::NXE`] must not
+    /// invalidate the currently active paging structures.
+    pub unsafe fn write(self) {
+        // SAFETY:
+        // The caller guarantees that `self` can be written to `IA32_EFER`.
+        unsafe { wrmsr(Self::MSR, self.0) }
+    }
+
+    /// Creates a new [`Efer`] from its raw bits.
+    pub const fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw bits of this [`Efer`].
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Returns `true` if system call extensions are enabled.
+    pub const fn sce(self) -> bool {
+        self.0 & Self::SCE == Self::SCE
+    }
+
+    /// Returns `true` if long mode is enabled.
+    pub const fn lme(self) -> bool {
+        self.0 & Self::LME == Self::LME
+    }
+
+    /// Returns `true` if long mode is active.
+    pub const fn lma(self) -> bool {
+        self.0 & Self::LMA == Self::LMA
+    }
+
+    /// Returns `true` if no-execute page protection is enabled.
+    pub const fn nxe(self) -> bool {
+        self.0 & Self::NXE == Self::NXE
+    }
+
+    /// Returns a copy of `self` with system call extensions set to `enabled`.
+    pub const fn set_sce(self, enabled: bool) -> Self {
+        self.set_bit(Self::SCE, enabled)
+    }
+
+    /// Returns a copy of `self` with long mode set to `enabled`.
+    pub const fn set_lme(self, enabled: bool) -> Self {
+        self.set_bit(Self::LME, enabled)
+    }
+
+    /// Returns a copy of `self` with no-execute page protection set to `enabled`.
+    pub const fn set_nxe(self, enabled: bool) -> Self {
+        self.set_bit(Self::NXE, enabled)
+    }
+
+    /// Returns a copy of `self` with the bits in `mask` set to `enabled`.
+    const fn set_bit(self, mask: u64, enabled: bool) -> Self {
+        if enabled {
+            Self(self.0 | mask)
+        } else {
+            Self(self.0 & !mask)
+        }
+    }
+}
+
+/// The value of the `IA32_APIC_BASE` model-specific register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ApicBase(u64);
+
+impl ApicBase {
+    /// The index of the `IA32_APIC_BASE` model-specific register.
+    pub const MSR: u32 = 0x1B;
+
+    /// Set if the current processor is the bootstrap processor.
+    pub const BSP: u64 = 1 << 8;
+    /// Set if x2APIC mode is enabled.
+    pub const X2APIC_ENABLE: u64 = 1 << 10;
+    /// Set if the local APIC is globally enabled.
+    pub const GLOBAL_ENABLE: u64 = 1 << 11;
+    /// Mask of the bits holding the physical base address of the local APIC.
+    pub const BASE_ADDRESS_MASK: u64 = 0x000F_FFFF_FFFF_F000;
+
+    /// Reads the current value of `IA32_APIC_BASE`.
+    ///
+    /// # Safety
+    ///
+    /// The processor must be executing at privilege level 0 and must implement
+    /// `IA32_APIC_BASE`.
+    pub unsafe fn read() -> Self {
+        // SAFETY:
+        // The caller guarantees that `IA32_APIC_BASE` can be read.
+        Self(unsafe { rdmsr(Self::MSR) })
+    }
+
+    /// Creates a new [`ApicBase`] from its raw bits.
+    pub const fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw bits of this [`ApicBase`].
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Returns `true` if the current processor is the bootstrap processor.
+    pub const fn is_bsp(self) -> bool {
+        self.0 & Self::BSP == Self::BSP
+    }
+
+    /// Returns `true` if x2APIC mode is enabled.
+    pub const fn x2apic_enabled(self) -> bool {
+        self.0 & Self::X2APIC_ENABLE == Self::X2APIC_ENABLE
+    }
+
+    /// Returns `true` if the local APIC is globally enabled.
+    pub const fn enabled(self) -> bool {
+        self.0 & Self::GLOBAL_ENABLE == Self::GLOBAL_ENABLE
+    }
+
+    /// Returns the physical base address of the local APIC.
+    pub const fn base_address(self) -> u64 {
+        self.0 & Self::BASE_ADDRESS_MASK
+    }
+}
@@ -0,0 +1,248 @@
+//! Detection of `x86` paging modes.
+
+use crate::{
+    cpuid::{cpuid, CpuidResult, EXTENDED_LEAF_BASE},
+    msr::Efer,
+    registers::{Cr0, Cr4},
+};
+
+/// The paging modes supported by `x86` processors.
+///
+/// Paging modes are ordered by capability, so [`PagingMode::Disabled`] is the least capable and
+/// [`PagingMode::Level5`] is the most capable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PagingMode {
+    /// Paging is disabled.
+    Disabled,
+    /// 32-bit paging.
+    Bits32,
+    /// PAE paging.
+    Pae,
+    /// 4-level paging.
+    Level4,
+    /// 5-level paging.
+    Level5,
+}
+
+impl PagingMode {
+    /// Returns the stable numeric encoding of this [`PagingMode`].
+    pub const fn as_u8(self) -> u8 {
+        match self {
+            Self::Disabled => 0,
+            Self::Bits32 => 1,
+            Self::Pae => 2,
+            Self::Level4 => 3,
+            Self::Level5 => 4,
+        }
+    }
+
+    /// Returns the [`PagingMode`] whose [`PagingMode::as_u8`] encoding is `value`.
+    pub const fn from_u8(value: u8) -> Option<Self> {
+        let mode = match value {
+            0 => Self::Disabled,
+            1 => Self::Bits32,
+            2 => Self::Pae,
+            3 => Self::Level4,
+            4 => Self::Level5,
+            _ => return None,
+        };
+
+        Some(mode)
+    }
+
+    /// Returns `true` if the processor must be in long mode to use this [`PagingMode`].
+    pub const fn requires_long_mode(self) -> bool {
+        matches!(self, Self::Level4 | Self::Level5)
+    }
+}
+
+/// Returns the [`PagingMode`] the processor is currently using.
+///
+/// # Safety
+///
+/// The processor must be executing at privilege level 0.
+pub unsafe fn current_paging_mode() -> PagingMode {
+    // SAFETY:
+    // The caller guarantees that control registers can be accessed.
+    let cr0 = unsafe { Cr0::read() };
+    // SAFETY:
+    // The caller guarantees that control registers can be accessed.
+    let cr4 = unsafe { Cr4::read() };
+    // SAFETY:
+    // The caller guarantees that model-specific registers can be accessed, and every `x86_64`
+    // processor implements `IA32_EFER`.
+    let efer = unsafe { Efer::read() };
+
+    paging_mode_from_registers(cr0, cr4, efer)
+}
+
+/// Determines the [`PagingMode`] selected by the given `CR0`, `CR4`, and `IA32_EFER` values.
+///
+/// `CR4.PAE` and `CR4.LA57` are ignored when `CR0.PG` is clear, and `CR4.LA57` is ignored unless
+/// `IA32_EFER.LMA` is set.
+pub const fn paging_mode_from_registers(cr0: Cr0, cr4: Cr4, efer: Efer) -> PagingMode {
+    if !cr0.pg() {
+        PagingMode::Disabled
+    } else if !cr4.pae() {
+        PagingMode::Bits32
+    } else if !efer.lma() {
+        PagingMode::Pae
+    } else if cr4.la57() {
+        PagingMode::Level5
+    } else {
+        PagingMode::Level4
+    }
+}
+
+/// `CPUID.01H:EDX.PAE`.
+const CPUID_PAE: u32 = 1 << 6;
+/// `CPUID.80000001H:EDX.LM`.
+const CPUID_LONG_MODE: u32 = 1 << 29;
+/// `CPUID.(EAX=07H,ECX=0H):ECX.LA57`.
+const CPUID_LA57: u32 = 1 << 16;
+
+/// Returns the most capable [`PagingMode`] the current processor supports.
+pub fn max_supported_paging_mode() -> PagingMode {
+    max_paging_mode_from_cpuid(cpuid(1, 0), cpuid(EXTENDED_LEAF_BASE + 1, 0), cpuid(7, 0))
+}
+
+/// Determines the most capable [`PagingMode`] supported by a processor that reports `leaf_1`,
+/// `extended_leaf_1`, and `leaf_7` (subleaf 0) from `CPUID`.
+///
+/// [`None`] indicates that the processor does not implement the corresponding leaf.
+pub fn max_paging_mode_from_cpuid(
+    leaf_1: Option<CpuidResult>,
+    extended_leaf_1: Option<CpuidResult>,
+    leaf_7: Option<CpuidResult>,
+) -> PagingMode {
+    let pae = leaf_1.is_some_and(|result| result.edx & CPUID_PAE == CPUID_PAE);
+    if !pae {
+        return PagingMode::Bits32;
+    }
+
+    let long_mode =
+        extended_leaf_1.is_some_and(|result| result.edx & CPUID_LONG_MODE == CPUID_LONG_MODE);
+    if !long_mode {
+        return PagingMode::Pae;
+    }
+
+    let la57 = leaf_7.is_some_and(|result| result.ecx & CPUID_LA57 == CPUID_LA57);
+    if !la57 {
+        return PagingMode::Level4;
+    }
+
+    PagingMode::Level5
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Determines the [`PagingMode`] selected by the given raw register values.
+    fn mode(cr0: u64, cr4: u64, efer: u64) -> PagingMode {
+        paging_mode_from_registers(
+            Cr0::from_bits(cr0),
+            Cr4::from_bits(cr4),
+            Efer::from_bits(efer),
+        )
+    }
+
+    #[test]
+    fn paging_disabled_ignores_pae() {
+        assert_eq!(mode(Cr0::PE, Cr4::PAE, 0), PagingMode::Disabled);
+    }
+
+    #[test]
+    fn paging_without_pae_is_bits32() {
+        assert_eq!(mode(Cr0::PE | Cr0::PG, 0, 0), PagingMode::Bits32);
+    }
+
+    #[test]
+    fn pae_without_lma_is_pae() {
+        assert_eq!(mode(Cr0::PE | Cr0::PG, Cr4::PAE, 0), PagingMode::Pae);
+    }
+
+    #[test]
+    fn lma_without_la57_is_level4() {
+        assert_eq!(
+            mode(Cr0::PE | Cr0::PG, Cr4::PAE, Efer::LME | Efer::LMA),
+            PagingMode::Level4
+        );
+    }
+
+    #[test]
+    fn lma_with_la57_is_level5() {
+        assert_eq!(
+            mode(
+                Cr0::PE | Cr0::PG,
+                Cr4::PAE | Cr4::LA57,
+                Efer::LME | Efer::LMA
+            ),
+            PagingMode::Level5
+        );
+    }
+
+    #[test]
+    fn la57_without_lma_is_pae() {
+        assert_eq!(
+            mode(Cr0::PE | Cr0::PG, Cr4::PAE | Cr4::LA57, 0),
+            PagingMode::Pae
+        );
+    }
+
+    /// Returns a [`CpuidResult`] with only `edx` set.
+    fn edx(edx: u32) -> Option<CpuidResult> {
+        Some(CpuidResult {
+            edx,
+            ..CpuidResult::default()
+        })
+    }
+
+    /// Returns a [`CpuidResult`] with only `ecx` set.
+    fn ecx(ecx: u32) -> Option<CpuidResult> {
+        Some(CpuidResult {
+            ecx,
+            ..CpuidResult::default()
+        })
+    }
+
+    #[test]
+    fn long_mode_without_la57_caps_at_level4() {
+        assert_eq!(
+            max_paging_mode_from_cpuid(edx(CPUID_PAE), edx(CPUID_LONG_MODE), ecx(0)),
+            PagingMode::Level4
+        );
+    }
+
+    #[test]
+    fn missing_leaf_7_caps_at_level4() {
+        assert_eq!(
+            max_paging_mode_from_cpuid(edx(CPUID_PAE), edx(CPUID_LONG_MODE), None),
+            PagingMode::Level4
+        );
+    }
+
+    #[test]
+    fn long_mode_with_la57_is_level5() {
+        assert_eq!(
+            max_paging_mode_from_cpuid(edx(CPUID_PAE), edx(CPUID_LONG_MODE), ecx(CPUID_LA57)),
+            PagingMode::Level5
+        );
+    }
+
+    #[test]
+    fn missing_long_mode_caps_at_pae() {
+        assert_eq!(
+            max_paging_mode_from_cpuid(edx(CPUID_PAE), edx(0), ecx(CPUID_LA57)),
+            PagingMode::Pae
+        );
+    }
+
+    #[test]
+    fn missing_pae_caps_at_bits32() {
+        assert_eq!(
+            max_paging_mode_from_cpuid(edx(0), edx(CPUID_LONG_MODE), ecx(CPUID_LA57)),
+            PagingMode::Bits32
+        );
+    }
+}
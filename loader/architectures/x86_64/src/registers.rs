@@ -0,0 +1,399 @@
+//! Access to segment, flags, and control registers.
+
+use core::arch::asm;
+
+/// A value loadable into a segment register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct SegmentSelector(u16);
+
+impl SegmentSelector {
+    /// The null segment selector.
+    pub const NULL: Self = Self(0);
+
+    /// Creates a new [`SegmentSelector`] referencing the descriptor at `index` in `table`, with a
+    /// requested privilege level of `rpl`.
+    ///
+    /// Bits of `index` and `rpl` that do not fit in their respective fields are discarded.
+    pub const fn new(index: u16, table: DescriptorTable, rpl: u8) -> Self {
+        let table_bit = match table {
+            DescriptorTable::Gdt => 0,
+            DescriptorTable::Ldt => 1 << 2,
+        };
+
+        Self((index << 3) | table_bit | (rpl as u16 & 0b11))
+    }
+
+    /// Creates a new [`SegmentSelector`] from its raw bits.
+    pub const fn from_bits(bits: u16) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw bits of this [`SegmentSelector`].
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the index of the descriptor this [`SegmentSelector`] references.
+    pub const fn index(self) -> u16 {
+        self.0 >> 3
+    }
+
+    /// Returns the descriptor table this [`SegmentSelector`] references.
+    pub const fn table(self) -> DescriptorTable {
+        if self.0 & (1 << 2) == 0 {
+            DescriptorTable::Gdt
+        } else {
+            DescriptorTable::Ldt
+        }
+    }
+
+    /// Returns the requested privilege level of this [`SegmentSelector`].
+    pub const fn rpl(self) -> u8 {
+        (self.0 & 0b11) as u8
+    }
+}
+
+/// The descriptor table a [`SegmentSelector`] references.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DescriptorTable {
+    /// The global descriptor table.
+    Gdt,
+    /// The local descriptor table.
+    Ldt,
+}
+
+/// Generates a function that reads the given segment register.
+macro_rules! read_segment {
+    ($name:ident, $register:literal) => {
+        #[doc = concat!("Returns the [`SegmentSelector`] currently loaded in `", $register, "`.")]
+        pub fn $name() -> SegmentSelector {
+            let selector: u16;
+
+            // SAFETY:
+            // Reading a segment register has no side effects.
+            unsafe {
+                asm!(
+                    concat!("mov {:x}, ", $register),
+                    out(reg) selector,
+                    options(nomem, nostack, preserves_flags)
+                )
+            }
+
+            SegmentSelector(selector)
+        }
+    };
+}
+
+/// Generates a function that loads the given segment register.
+macro_rules! write_segment {
+    ($name:ident, $register:literal) => {
+        #[doc = concat!("Loads `selector` into `", $register, "`.")]
+        ///
+        /// # Safety
+        ///
+        /// `selector` must reference a descriptor that is valid for this segment register, and
+        /// loading it must not invalidate any assumptions the rest of the program relies upon.
+        pub unsafe fn $name(selector: SegmentSelector) {
+            // SAFETY:
+            // The caller guarantees that `selector` is valid for this segment register.
+            unsafe {
+                asm!(
+                    concat!("mov ", $register, ", {:x}"),
+                    in(reg) selector.0,
+                    options(nostack, preserves_flags)
+                )
+            }
+        }
+    };
+}
+
+read_segment!(read_cs, "cs");
+read_segment!(read_ds, "ds");
+read_segment!(read_es, "es");
+read_segment!(read_fs, "fs");
+read_segment!(read_gs, "gs");
+read_segment!(read_ss, "ss");
+
+write_segment!(write_ds, "ds");
+write_segment!(write_es, "es");
+write_segment!(write_fs, "fs");
+write_segment!(write_gs, "gs");
+write_segment!(write_ss, "ss");
+
+/// The value of the `RFLAGS` register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct RFlags(u64);
+
+impl RFlags {
+    /// Carry flag.
+    pub const CF: u64 = 1 << 0;
+    /// Parity flag.
+    pub const PF: u64 = 1 << 2;
+    /// Auxiliary carry flag.
+    pub const AF: u64 = 1 << 4;
+    /// Zero flag.
+    pub const ZF: u64 = 1 << 6;
+    /// Sign flag.
+    pub const SF: u64 = 1 << 7;
+    /// Trap flag.
+    pub const TF: u64 = 1 << 8;
+    /// Interrupt enable flag.
+    pub const IF: u64 = 1 << 9;
+    /// Direction flag.
+    pub const DF: u64 = 1 << 10;
+    /// Overflow flag.
+    pub const OF: u64 = 1 << 11;
+    /// Mask of the I/O privilege level field.
+    pub const IOPL_MASK: u64 = 0b11 << 12;
+    /// Nested task flag.
+    pub const NT: u64 = 1 << 14;
+    /// Resume flag.
+    pub const RF: u64 = 1 << 16;
+    /// Virtual-8086 mode flag.
+    pub const VM: u64 = 1 << 17;
+    /// Alignment check / access control flag.
+    pub const AC: u64 = 1 << 18;
+    /// Virtual interrupt flag.
+    pub const VIF: u64 = 1 << 19;
+    /// Virtual interrupt pending flag.
+    pub const VIP: u64 = 1 << 20;
+    /// `CPUID` availability flag.
+    pub const ID: u64 = 1 << 21;
+
+    /// Creates a new [`RFlags`] from its raw bits.
+    pub const fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw bits of this [`RFlags`].
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Returns `true` if the carry flag is set.
+    pub const fn cf(self) -> bool {
+        self.0 & Self::CF == Self::CF
+    }
+
+    /// Returns `true` if the parity flag is set.
+    pub const fn pf(self) -> bool {
+        self.0 & Self::PF == Self::PF
+    }
+
+    /// Returns `true` if the auxiliary carry flag is set.
+    pub const fn af(self) -> bool {
+        self.0 & Self::AF == Self::AF
+    }
+
+    /// Returns `true` if the zero flag is set.
+    pub const fn zf(self) -> bool {
+        self.0 & Self::ZF == Self::ZF
+    }
+
+    /// Returns `true` if the sign flag is set.
+    pub const fn sf(self) -> bool {
+        self.0 & Self::SF == Self::SF
+    }
+
+    /// Returns `true` if the trap flag is set.
+    pub const fn tf(self) -> bool {
+        self.0 & Self::TF == Self::TF
+    }
+
+    /// Returns `true` if the interrupt enable flag is set.
+    ///
+    /// This is not named `if` since that is a keyword.
+    pub const fn interrupt_flag(self) -> bool {
+        self.0 & Self::IF == Self::IF
+    }
+
+    /// Returns `true` if the direction flag is set.
+    pub const fn df(self) -> bool {
+        self.0 & Self::DF == Self::DF
+    }
+
+    /// Returns `true` if the overflow flag is set.
+    pub const fn of(self) -> bool {
+        self.0 & Self::OF == Self::OF
+    }
+
+    /// Returns `true` if the nested task flag is set.
+    pub const fn nt(self) -> bool {
+        self.0 & Self::NT == Self::NT
+    }
+
+    /// Returns `true` if the resume flag is set.
+    pub const fn rf(self) -> bool {
+        self.0 & Self::RF == Self::RF
+    }
+
+    /// Returns `true` if virtual-8086 mode is enabled.
+    pub const fn vm(self) -> bool {
+        self.0 & Self::VM == Self::VM
+    }
+
+    /// Returns `true` if the alignment check / access control flag is set.
+    pub const fn ac(self) -> bool {
+        self.0 & Self::AC == Self::AC
+    }
+
+    /// Returns `true` if the virtual interrupt flag is set.
+    pub const fn vif(self) -> bool {
+        self.0 & Self::VIF == Self::VIF
+    }
+
+    /// Returns `true` if a virtual interrupt is pending.
+    pub const fn vip(self) -> bool {
+        self.0 & Self::VIP == Self::VIP
+    }
+
+    /// Returns `true` if the `CPUID` availability flag is set.
+    pub const fn id(self) -> bool {
+        self.0 & Self::ID == Self::ID
+    }
+
+    /// Returns the I/O privilege level.
+    pub const fn iopl(self) -> u8 {
+        ((self.0 & Self::IOPL_MASK) >> 12) as u8
+    }
+}
+
+/// Returns the current value of `RFLAGS`.
+pub fn read_rflags() -> RFlags {
+    let rflags: u64;
+
+    // SAFETY:
+    // Pushing and popping `RFLAGS` leaves the stack and flags unchanged.
+    unsafe {
+        asm!(
+            "pushfq",
+            "pop {}",
+            out(reg) rflags,
+            options(nomem, preserves_flags)
+        )
+    }
+
+    RFlags(rflags)
+}
+
+/// Returns the current value of `EFLAGS`, the low 32 bits of `RFLAGS`.
+pub fn read_eflags() -> RFlags {
+    RFlags(read_rflags().0 & 0xFFFF_FFFF)
+}
+
+/// The value of the `CR0` control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Cr0(u64);
+
+impl Cr0 {
+    /// Protection enable.
+    pub const PE: u64 = 1 << 0;
+    /// Write protect.
+    pub const WP: u64 = 1 << 16;
+    /// Paging enable.
+    pub const PG: u64 = 1 << 31;
+
+    /// Reads the current value of `CR0`.
+    ///
+    /// # Safety
+    ///
+    /// The processor must be executing at privilege level 0.
+    pub unsafe fn read() -> Self {
+        let cr0: u64;
+
+        // SAFETY:
+        // The caller guarantees that control registers can be accessed.
+        unsafe { asm!("mov {}, cr0", out(reg) cr0, options(nomem, nostack, preserves_flags)) }
+
+        Self(cr0)
+    }
+
+    /// Creates a new [`Cr0`] from its raw bits.
+    pub const fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw bits of this [`Cr0`].
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Returns `true` if protected mode is enabled.
+    pub const fn pe(self) -> bool {
+        self.0 & Self::PE == Self::PE
+    }
+
+    /// Returns `true` if supervisor writes to read-only pages are prevented.
+    pub const fn wp(self) -> bool {
+        self.0 & Self::WP == Self::WP
+    }
+
+    /// Returns `true` if paging is enabled.
+    pub const fn pg(self) -> bool {
+        self.0 & Self::PG == Self::PG
+    }
+}
+
+/// The value of the `CR4` control register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Cr4(u64);
+
+impl Cr4 {
+    /// Page size extensions.
+    pub const PSE: u64 = 1 << 4;
+    /// Physical address extension.
+    pub const PAE: u64 = 1 << 5;
+    /// Page global enable.
+    pub const PGE: u64 = 1 << 7;
+    /// 57-bit linear addresses.
+    pub const LA57: u64 = 1 << 12;
+
+    /// Reads the current value of `CR4`.
+    ///
+    /// # Safety
+    ///
+    /// The processor must be executing at privilege level 0.
+    pub unsafe fn read() -> Self {
+        let cr4: u64;
+
+        // SAFETY:
+        // The caller guarantees that control registers can be accessed.
+        unsafe { asm!("mov {}, cr4", out(reg) cr4, options(nomem, nostack, preserves_flags)) }
+
+        Self(cr4)
+    }
+
+    /// Creates a new [`Cr4`] from its raw bits.
+    pub const fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw bits of this [`Cr4`].
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Returns `true` if page size extensions are enabled.
+    pub const fn pse(self) -> bool {
+        self.0 & Self::PSE == Self::PSE
+    }
+
+    /// Returns `true` if physical address extension is enabled.
+    pub const fn pae(self) -> bool {
+        self.0 & Self::PAE == Self::PAE
+    }
+
+    /// Returns `true` if global pages are enabled.
+    pub const fn pge(self) -> bool {
+        self.0 & Self::PGE == Self::PGE
+    }
+
+    /// Returns `true` if 57-bit linear addresses are enabled.
+    pub const fn la57(self) -> bool {
+        self.0 & Self::LA57 == Self::LA57
+    }
+}
@@ -0,0 +1,163 @@
+//! Driver for 16550-compatible UARTs.
+
+use core::fmt;
+
+use loader::logging::LogSink;
+
+use crate::io_port::Port;
+
+/// Offset of the transmit holding register, or the low byte of the divisor latch when `DLAB` is
+/// set.
+const DATA: u16 = 0;
+/// Offset of the interrupt enable register, or the high byte of the divisor latch when `DLAB` is
+/// set.
+const INTERRUPT_ENABLE: u16 = 1;
+/// Offset of the FIFO control register.
+const FIFO_CONTROL: u16 = 2;
+/// Offset of the line control register.
+const LINE_CONTROL: u16 = 3;
+/// Offset of the modem control register.
+const MODEM_CONTROL: u16 = 4;
+/// Offset of the line status register.
+const LINE_STATUS: u16 = 5;
+
+/// Line control value that selects the divisor latch.
+const LINE_CONTROL_DLAB: u8 = 1 << 7;
+/// Line control value selecting 8 data bits, no parity, and 1 stop bit.
+const LINE_CONTROL_8N1: u8 = 0b11;
+/// FIFO control value that enables and clears the FIFOs with a 14-byte threshold.
+const FIFO_CONTROL_ENABLE: u8 = 0xC7;
+/// Modem control value that asserts `DTR` and `RTS`.
+const MODEM_CONTROL_DTR_RTS: u8 = 0b11;
+/// Line status bit set when the transmit holding register is empty.
+const LINE_STATUS_TRANSMIT_EMPTY: u8 = 1 << 5;
+
+/// The frequency, in hertz, of the UART's baud rate generator divided by 16.
+const MAX_BAUD_RATE: u32 = 115_200;
+
+/// A 16550-compatible UART accessed through I/O ports.
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct SerialPort {
+    /// The I/O port of the UART's first register.
+    base: u16,
+}
+
+impl SerialPort {
+    /// The conventional base I/O port of `COM1`.
+    pub const COM1: u16 = 0x3F8;
+
+    /// Creates a new [`SerialPort`] whose registers start at the I/O port `base`.
+    ///
+    /// The UART may also be driven by other software, such as the firmware's console while boot
+    /// services are running. In that case output from both is interleaved, and
+    /// [`SerialPort::init`] reconfigures the UART for all of its users.
+    ///
+    /// # Safety
+    ///
+    /// `base` must be the base I/O port of a 16550-compatible UART, and accessing its registers
+    /// must not violate any invariants the rest of the program relies upon.
+    pub const unsafe fn new(base: u16) -> Self {
+        Self { base }
+    }
+
+    /// Creates a new [`SerialPort`] for the UART at [`SerialPort::COM1`].
+    ///
+    /// # Safety
+    ///
+    /// The requirements of [`SerialPort::new`] must hold for [`SerialPort::COM1`].
+    pub const unsafe fn com1() -> Self {
+        // SAFETY:
+        // The caller upholds the requirements of `SerialPort::new` for `COM1`.
+        unsafe { Self::new(Self::COM1) }
+    }
+
+    /// Returns the base I/O port of this [`SerialPort`].
+    pub const fn base(&self) -> u16 {
+        self.base
+    }
+
+    /// Initializes this [`SerialPort`] for polled transmission at `baud_rate` with 8 data bits,
+    /// no parity, and 1 stop bit.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidBaudRateError`] if `baud_rate` is zero or cannot be produced by the
+    /// UART's baud rate generator.
+    pub fn init(&self, baud_rate: u32) -> Result<(), InvalidBaudRateError> {
+        if baud_rate == 0 || !MAX_BAUD_RATE.is_multiple_of(baud_rate) {
+            return Err(InvalidBaudRateError(baud_rate));
+        }
+        let [divisor_low, divisor_high, ..] = (MAX_BAUD_RATE / baud_rate).to_le_bytes();
+
+        self.write_register(INTERRUPT_ENABLE, 0);
+        self.write_register(LINE_CONTROL, LINE_CONTROL_DLAB);
+        self.write_register(DATA, divisor_low);
+        self.write_register(INTERRUPT_ENABLE, divisor_high);
+        self.write_register(LINE_CONTROL, LINE_CONTROL_8N1);
+        self.write_register(FIFO_CONTROL, FIFO_CONTROL_ENABLE);
+        self.write_register(MODEM_CONTROL, MODEM_CONTROL_DTR_RTS);
+
+        Ok(())
+    }
+
+    /// Transmits `byte`, waiting until the UART is ready to accept it.
+    pub fn write_byte(&self, byte: u8) {
+        while self.read_register(LINE_STATUS) & LINE_STATUS_TRANSMIT_EMPTY == 0 {
+            core::hint::spin_loop();
+        }
+
+        self.write_register(DATA, byte);
+    }
+
+    /// Transmits `bytes`, translating each `\n` into `\r\n`.
+    pub fn write_bytes(&self, bytes: &[u8]) {
+        for &byte in bytes {
+            if byte == b'\n' {
+                self.write_byte(b'\r');
+            }
+
+            self.write_byte(byte);
+        }
+    }
+
+    /// Reads the register at `offset`.
+    fn read_register(&self, offset: u16) -> u8 {
+        // SAFETY:
+        // The invariants of [`SerialPort::new`] guarantee that `self.base + offset` is a register
+        // of a 16550-compatible UART.
+        unsafe { Port::<u8>::new(self.base + offset).read() }
+    }
+
+    /// Writes `value` to the register at `offset`.
+    fn write_register(&self, offset: u16, value: u8) {
+        // SAFETY:
+        // The invariants of [`SerialPort::new`] guarantee that `self.base + offset` is a register
+        // of a 16550-compatible UART.
+        unsafe { Port::<u8>::new(self.base + offset).write(value) }
+    }
+}
+
+impl fmt::Write for &SerialPort {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_bytes(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl LogSink for SerialPort {
+    fn write_fmt(&self, args: fmt::Arguments) {
+        let _ = fmt::Write::write_fmt(&mut &*self, args);
+    }
+}
+
+/// Error returned by [`SerialPort::init`] when the requested baud rate is not supported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct InvalidBaudRateError(pub u32);
+
+impl fmt::Display for InvalidBaudRateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unsupported baud rate: {}", self.0)
+    }
+}
+
+impl core::error::Error for InvalidBaudRateError {}
@@ -4,3 +4,5 @@
 //! other loader crates use.
 
 #![no_std]
+
+pub mod logging;
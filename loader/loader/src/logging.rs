@@ -0,0 +1,294 @@
+//! Logging facilities for the `tvm` loader.
+//!
+//! Messages are emitted using [`log_error!`][crate::log_error], [`log_warn!`][crate::log_warn],
+//! [`log_info!`][crate::log_info], [`log_debug!`][crate::log_debug], and
+//! [`log_trace!`][crate::log_trace]. Messages less important than [`max_level()`] are discarded
+//! before they are formatted. Messages that are emitted are written to the [`LogSink`] installed
+//! by [`set_logger()`], and are discarded until one is installed. Each message can optionally be
+//! prefixed with a timestamp using [`set_timestamp_source()`], which is displayed in seconds once
+//! its frequency is provided using [`set_timestamp_frequency()`].
+
+use core::{
+    cell::UnsafeCell,
+    fmt,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+/// A destination for log messages.
+///
+/// Sinks are shared by every processor that logs, so they must be [`Sync`].
+pub trait LogSink: Sync {
+    /// Writes `args` to the sink.
+    fn write_fmt(&self, args: fmt::Arguments);
+
+    /// Flushes any output buffered by the sink.
+    fn flush(&self) {}
+}
+
+/// A [`LogSink`] that discards all messages.
+struct NopSink;
+
+impl LogSink for NopSink {
+    fn write_fmt(&self, _: fmt::Arguments) {}
+}
+
+/// Storage for logging configuration that is only modified while no logging is in progress.
+struct ConfigCell<T>(UnsafeCell<T>);
+
+// SAFETY:
+// Every function that writes a `ConfigCell` requires that it is not called concurrently with any
+// other logging operation, so concurrent accesses only read the stored `T`, which is sound since
+// `T` is `Sync`.
+unsafe impl<T: Sync> Sync for ConfigCell<T> {}
+
+/// The [`LogSink`] that emitted messages are written to.
+static LOGGER: ConfigCell<&'static dyn LogSink> = ConfigCell(UnsafeCell::new(&NopSink));
+
+/// The counter read to timestamp emitted messages.
+static TIMESTAMP_SOURCE: ConfigCell<Option<fn() -> u64>> = ConfigCell(UnsafeCell::new(None));
+
+/// The number of ticks per second of [`TIMESTAMP_SOURCE`], or `0` if the frequency is unknown.
+static TIMESTAMP_FREQUENCY: ConfigCell<u64> = ConfigCell(UnsafeCell::new(0));
+
+/// Installs `logger` as the [`LogSink`] that emitted messages are written to.
+///
+/// # Safety
+///
+/// This function must not be called concurrently with any other logging operation.
+pub unsafe fn set_logger(logger: &'static dyn LogSink) {
+    // SAFETY:
+    // The caller guarantees that no other logging operation is accessing `LOGGER`.
+    unsafe { *LOGGER.0.get() = logger }
+}
+
+/// Returns the installed [`LogSink`].
+fn logger() -> &'static dyn LogSink {
+    // SAFETY:
+    // `LOGGER` is only written by [`set_logger()`], which may not run concurrently with this.
+    unsafe { *LOGGER.0.get() }
+}
+
+/// Prefixes emitted messages with the monotonic counter value returned by `source`, such as the
+/// processor's timestamp counter. Passing [`None`] disables timestamps.
+///
+/// # Safety
+///
+/// This function must not be called concurrently with any other logging operation.
+pub unsafe fn set_timestamp_source(source: Option<fn() -> u64>) {
+    // SAFETY:
+    // The caller guarantees that no other logging operation is accessing `TIMESTAMP_SOURCE`.
+    unsafe { *TIMESTAMP_SOURCE.0.get() = source }
+}
+
+/// Returns the installed timestamp source.
+fn timestamp_source() -> Option<fn() -> u64> {
+    // SAFETY:
+    // `TIMESTAMP_SOURCE` is only written by [`set_timestamp_source()`], which may not run
+    // concurrently with this.
+    unsafe { *TIMESTAMP_SOURCE.0.get() }
+}
+
+/// Sets the number of ticks per second of the counter installed by [`set_timestamp_source()`],
+/// such as a calibrated timestamp counter frequency.
+///
+/// When `frequency` is non-zero, timestamps are displayed as seconds and milliseconds since the
+/// counter started rather than as raw counter values. Passing `0` restores raw counter values.
+///
+/// # Safety
+///
+/// This function must not be called concurrently with any other logging operation.
+pub unsafe fn set_timestamp_frequency(frequency: u64) {
+    // SAFETY:
+    // The caller guarantees that no other logging operation is accessing `TIMESTAMP_FREQUENCY`.
+    unsafe { *TIMESTAMP_FREQUENCY.0.get() = frequency }
+}
+
+/// Returns the frequency set by [`set_timestamp_frequency()`].
+fn timestamp_frequency() -> u64 {
+    // SAFETY:
+    // `TIMESTAMP_FREQUENCY` is only written by [`set_timestamp_frequency()`], which may not run
+    // concurrently with this.
+    unsafe { *TIMESTAMP_FREQUENCY.0.get() }
+}
+
+/// Splits `ticks` of a counter running at `frequency` ticks per second into whole seconds and
+/// the remaining milliseconds.
+///
+/// `frequency` must be non-zero.
+const fn split_timestamp(ticks: u64, frequency: u64) -> (u64, u64) {
+    let seconds = ticks / frequency;
+    let milliseconds = (ticks % frequency) as u128 * 1000 / frequency as u128;
+
+    (seconds, milliseconds as u64)
+}
+
+/// Flushes any output buffered by the installed [`LogSink`].
+pub fn flush() {
+    logger().flush()
+}
+
+/// The importance of a log message.
+///
+/// Levels are ordered by verbosity, so [`Level::Error`] is the least verbose and
+/// [`Level::Trace`] is the most verbose.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum Level {
+    /// Serious errors.
+    Error,
+    /// Potentially hazardous situations.
+    Warn,
+    /// Useful information.
+    Info,
+    /// Lower priority information.
+    Debug,
+    /// Very low priority, often extremely verbose, information.
+    Trace,
+}
+
+impl Level {
+    /// Converts a value produced by `level as u8` back into a [`Level`].
+    const fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Error,
+            1 => Self::Warn,
+            2 => Self::Info,
+            3 => Self::Debug,
+            _ => Self::Trace,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Error => "ERROR",
+            Self::Warn => "WARN",
+            Self::Info => "INFO",
+            Self::Debug => "DEBUG",
+            Self::Trace => "TRACE",
+        };
+
+        f.pad(name)
+    }
+}
+
+/// The most verbose [`Level`] that is currently emitted.
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(Level::Debug as u8);
+
+/// Sets the most verbose [`Level`] that will be emitted.
+pub fn set_max_level(level: Level) {
+    MAX_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Returns the most verbose [`Level`] that will be emitted.
+pub fn max_level() -> Level {
+    Level::from_u8(MAX_LEVEL.load(Ordering::Relaxed))
+}
+
+/// Returns `true` if messages at `level` will be emitted.
+pub fn enabled(level: Level) -> bool {
+    level <= max_level()
+}
+
+/// Emits `args` at `level`.
+///
+/// This is an implementation detail of the logging macros and does not check [`enabled()`].
+#[doc(hidden)]
+pub fn log(level: Level, args: fmt::Arguments) {
+    let Some(source) = timestamp_source() else {
+        logger().write_fmt(format_args!("[{level:>5}] {args}\n"));
+        return;
+    };
+
+    let ticks = source();
+    let frequency = timestamp_frequency();
+    if frequency == 0 {
+        logger().write_fmt(format_args!("[{ticks:>20}] [{level:>5}] {args}\n"));
+    } else {
+        let (seconds, milliseconds) = split_timestamp(ticks, frequency);
+
+        logger().write_fmt(format_args!(
+            "[{seconds:>6}.{milliseconds:03}] [{level:>5}] {args}\n"
+        ));
+    }
+}
+
+/// Logs a message at the given [`Level`].
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {{
+        let level = $level;
+        if $crate::logging::enabled(level) {
+            $crate::logging::log(level, format_args!($($arg)+));
+        }
+    }};
+}
+
+/// Logs a message at [`Level::Error`].
+#[macro_export]
+macro_rules! log_error {
+    ($($arg:tt)+) => {
+        $crate::log!($crate::logging::Level::Error, $($arg)+)
+    };
+}
+
+/// Logs a message at [`Level::Warn`].
+#[macro_export]
+macro_rules! log_warn {
+    ($($arg:tt)+) => {
+        $crate::log!($crate::logging::Level::Warn, $($arg)+)
+    };
+}
+
+/// Logs a message at [`Level::Info`].
+#[macro_export]
+macro_rules! log_info {
+    ($($arg:tt)+) => {
+        $crate::log!($crate::logging::Level::Info, $($arg)+)
+    };
+}
+
+/// Logs a message at [`Level::Debug`].
+#[macro_export]
+macro_rules! log_debug {
+    ($($arg:tt)+) => {
+        $crate::log!($crate::logging::Level::Debug, $($arg)+)
+    };
+}
+
+/// Logs a message at [`Level::Trace`].
+#[macro_export]
+macro_rules! log_trace {
+    ($($arg:tt)+) => {
+        $crate::log!($crate::logging::Level::Trace, $($arg)+)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn just_below_one_second() {
+        assert_eq!(split_timestamp(999_999, 1_000_000), (0, 999));
+    }
+
+    #[test]
+    fn exactly_one_second() {
+        assert_eq!(split_timestamp(1_000_000, 1_000_000), (1, 0));
+    }
+
+    #[test]
+    fn just_above_one_second() {
+        assert_eq!(split_timestamp(1_001_000, 1_000_000), (1, 1));
+    }
+
+    #[test]
+    fn large_tick_counts_do_not_overflow() {
+        assert_eq!(
+            split_timestamp(u64::MAX, 3_000_000_000),
+            (6_148_914_691, 236)
+        );
+    }
+}
@@ -3,14 +3,41 @@
 #![no_std]
 #![no_main]
 
+use loader::{log_info, logging};
+use loader_x86_64::{
+    instructions::{halt_loop, rdtsc},
+    serial::SerialPort,
+};
+
+/// The serial port that log messages are written to.
+// SAFETY:
+// `COM1` is the conventional location of a 16550-compatible UART on `x86_64` PCs. The firmware's
+// console may also drive it while boot services are running, which `SerialPort::new` permits, and
+// nothing else in the loader relies on its configuration.
+static SERIAL: SerialPort = unsafe { SerialPort::com1() };
+
 /// Entry point to UEFI binary.
 #[no_mangle]
 pub extern "efiapi" fn efi_main() -> usize {
+    // This reprograms the UART underneath the firmware's console. Firmware serial consoles
+    // conventionally use the same 115200 baud 8N1 settings, so their output stays readable.
+    if SERIAL.init(115_200).is_ok() {
+        // SAFETY:
+        // No other logging operations are in progress.
+        unsafe { logging::set_logger(&SERIAL) }
+    }
+
+    // SAFETY:
+    // No other logging operations are in progress.
+    unsafe { logging::set_timestamp_source(Some(rdtsc)) }
+
+    log_info!("tvm loader started");
+
     0
 }
 
 /// Panic handler for `tvm` loader for `x86_64` UEFI systems.
 #[panic_handler]
 pub fn panic_handler(_: &core::panic::PanicInfo) -> ! {
-    loop {}
+    halt_loop()
 }